@@ -1,9 +1,344 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use yew::prelude::*;
-use wasm_bindgen_futures::spawn_local;
-use web_sys::{HtmlInputElement, Element};
-use wasm_bindgen::JsCast;
+use yew_router::prelude::*;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{HtmlInputElement, Element, Request, RequestInit, RequestMode, Response, ReadableStreamDefaultReader, TextDecoder, TextDecodeOptions};
+use wasm_bindgen::{JsCast, JsValue};
+use gloo::storage::{LocalStorage, Storage};
+use gloo::history::{BrowserHistory, History};
+use js_sys::{Date, Reflect, Uint8Array};
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+
+// Key under which the query history is persisted across page reloads.
+const HISTORY_STORAGE_KEY: &str = "rag_query_history";
+
+// ===== ROUTING =====
+
+#[derive(Clone, Routable, PartialEq)]
+enum Route {
+    #[at("/")]
+    Home,
+    #[at("/about")]
+    About,
+    #[at("/contacts")]
+    Contacts,
+    #[not_found]
+    #[at("/404")]
+    NotFound,
+}
+
+// Query-string shape for a shareable search permalink, e.g. `/?q=...&top_k=5&backend=phi3`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+struct QueryParams {
+    q: Option<String>,
+    top_k: Option<u32>,
+    backend: Option<String>,
+}
+
+/// Runs a query against the backend and drives the same state the submit
+/// button does, so a permalink load can reuse it without a form submission.
+/// `models` is an owned snapshot rather than the reactive state handle, since
+/// a caller that just fetched the model list (e.g. the mount-time effect)
+/// can't rely on a `UseStateHandle::set` from the same spawn_local landing in
+/// time for a synchronous read here.
+fn run_query(
+    query_text: String,
+    top_k_value: u32,
+    model_value: String,
+    models: Vec<ModelInfo>,
+    answer: UseStateHandle<String>,
+    is_loading: UseStateHandle<bool>,
+    error_message: UseStateHandle<Option<String>>,
+    last_backend: UseStateHandle<String>,
+    history: UseStateHandle<Vec<HistoryEntry>>,
+    sources: UseStateHandle<Vec<SourceDoc>>,
+) {
+    error_message.set(None);
+    is_loading.set(true);
+    answer.set("Generating answer...".to_string());
+    sources.set(Vec::new());
+
+    let use_openai_value = models
+        .iter()
+        .find(|m| m.id == model_value)
+        .map(|m| m.backend == "openai")
+        .unwrap_or(false);
+    let query_for_history = query_text.clone();
+
+    spawn_local(async move {
+        let payload = QueryRequest {
+            query: query_text.clone(),
+            top_k: top_k_value,
+            model: model_value,
+            use_openai: use_openai_value,
+        };
+
+        let streamed_so_far = std::cell::RefCell::new(String::new());
+        let on_token = |token: &str| {
+            streamed_so_far.borrow_mut().push_str(token);
+            answer.set(streamed_so_far.borrow().clone());
+        };
+
+        match fetch_generate(&payload, &on_token).await {
+            Ok(GenerateOutcome::Streamed(full_answer)) => {
+                answer.set(full_answer.clone());
+                error_message.set(None);
+                let backend_name = if use_openai_value { "OpenAI GPT-4" } else { "Ollama" }.to_string();
+                last_backend.set(backend_name.clone());
+
+                let mut updated_history = (*history).clone();
+                updated_history.push(HistoryEntry {
+                    query: query_for_history,
+                    answer: full_answer,
+                    backend: backend_name,
+                    top_k: top_k_value,
+                    timestamp: Date::new_0().to_iso_string().as_string().unwrap_or_default(),
+                });
+                if let Err(e) = LocalStorage::set(HISTORY_STORAGE_KEY, &updated_history) {
+                    web_sys::console::error_1(&format!("Failed to persist history: {:?}", e).into());
+                }
+                history.set(updated_history);
+            }
+            Ok(GenerateOutcome::Json(api_response)) => {
+                // Log the response for debugging
+                web_sys::console::log_1(&format!("Received answer: {}", &api_response.answer).into());
+                let backend_name = api_response.backend.clone().unwrap_or_else(|| "None".to_string());
+                answer.set(api_response.answer.clone());
+                if let Some(backend) = api_response.backend {
+                    last_backend.set(backend);
+                }
+                sources.set(api_response.sources.clone().unwrap_or_default());
+                error_message.set(None);
+
+                let mut updated_history = (*history).clone();
+                updated_history.push(HistoryEntry {
+                    query: query_for_history,
+                    answer: api_response.answer,
+                    backend: backend_name,
+                    top_k: top_k_value,
+                    timestamp: Date::new_0().to_iso_string().as_string().unwrap_or_default(),
+                });
+                if let Err(e) = LocalStorage::set(HISTORY_STORAGE_KEY, &updated_history) {
+                    web_sys::console::error_1(&format!("Failed to persist history: {:?}", e).into());
+                }
+                history.set(updated_history);
+            }
+            Err(e) => {
+                error_message.set(Some(e));
+                answer.set("Error generating answer.".to_string());
+            }
+        }
+
+        is_loading.set(false);
+    });
+}
+
+fn switch(route: Route) -> Html {
+    match route {
+        Route::Home => html! { <App /> },
+        Route::About => html! { <AboutPage /> },
+        Route::Contacts => html! { <ContactsPage /> },
+        Route::NotFound => html! { <h2>{ "404 — page not found" }</h2> },
+    }
+}
+
+/// The two ways `fetch_generate` can resolve a successful `/generate/` call,
+/// depending on which `Content-Type` the server answered with.
+enum GenerateOutcome {
+    Streamed(String),
+    Json(ApiResponse),
+}
+
+/// Renders `markdown` to HTML via `pulldown-cmark` and sanitizes it with
+/// `ammonia` before it is ever handed to `set_inner_html`, so LLM output can't
+/// inject arbitrary markup while still rendering lists, code blocks, etc.
+/// `source_count` bracketed references (e.g. `[1]`) are turned into
+/// superscript anchors that jump to the matching entry in the sources list.
+/// The substitution runs on the parser's `Text` events rather than on the
+/// rendered HTML string, and is skipped inside code blocks, so a literal
+/// `[1]` in a code sample isn't mistaken for a citation marker.
+fn render_answer_html(markdown: &str, source_count: usize) -> String {
+    let mut html_output = String::new();
+    let mut in_code_block = false;
+
+    // The parser emits unmatched `[` / `]` as separate `Text` events; merge
+    // adjacent text so a `[1]` marker arrives in one piece.
+    let events = pulldown_cmark::TextMergeStream::new(pulldown_cmark::Parser::new(markdown)).map(|event| match event {
+        pulldown_cmark::Event::Start(pulldown_cmark::Tag::CodeBlock(_)) => {
+            in_code_block = true;
+            event
+        }
+        pulldown_cmark::Event::End(pulldown_cmark::TagEnd::CodeBlock) => {
+            in_code_block = false;
+            event
+        }
+        pulldown_cmark::Event::Text(text) if !in_code_block => {
+            pulldown_cmark::Event::Html(link_citations(&text, source_count).into())
+        }
+        other => other,
+    });
+    pulldown_cmark::html::push_html(&mut html_output, events);
+
+    ammonia::clean(&html_output)
+}
+
+/// HTML-escapes `text`, then replaces `[1]`..`[source_count]` markers in it
+/// with superscript anchors. Only called on parser `Text` events (never on
+/// `Code`/`CodeBlock` content), so markers inside code aren't touched.
+fn link_citations(text: &str, source_count: usize) -> String {
+    let mut escaped = escape_html_text(text);
+    for i in 1..=source_count {
+        let marker = format!("[{}]", i);
+        let citation = format!("<sup><a href=\"#source-{0}\">{0}</a></sup>", i);
+        escaped = escaped.replace(&marker, &citation);
+    }
+    escaped
+}
+
+/// Minimal HTML-escaping for text that's about to be re-wrapped as a
+/// `pulldown_cmark::Event::Html` node, which (unlike `Event::Text`) isn't
+/// escaped automatically by `push_html`.
+fn escape_html_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Returns `uri` only if it's an `http(s)` link, so a retrieved document's
+/// URI (not user-authored, same trust level as the LLM answer text) can't
+/// smuggle a `javascript:` scheme into an anchor's `href`.
+fn safe_external_href(uri: &str) -> Option<&str> {
+    if uri.starts_with("http://") || uri.starts_with("https://") {
+        Some(uri)
+    } else {
+        None
+    }
+}
+
+/// Issues a single `/generate/` request via the `web_sys` Fetch API (reqwest
+/// can't stream response bodies in WASM, and the server may or may not stream
+/// back depending on its own config), then branches on the response's
+/// `Content-Type` to decide how to consume the one response we got: stream
+/// tokens as they arrive for `text/event-stream`, or parse the already-
+/// received body as JSON otherwise. This deliberately makes exactly one
+/// request — checking `Content-Type` up front and then re-issuing the call
+/// would run the backend's retrieval + LLM pipeline twice per query.
+async fn fetch_generate(payload: &QueryRequest, on_token: &dyn Fn(&str)) -> Result<GenerateOutcome, String> {
+    let body = serde_json::to_string(payload).map_err(|e| e.to_string())?;
+
+    let opts = RequestInit::new();
+    opts.set_method("POST");
+    opts.set_mode(RequestMode::Cors);
+    opts.set_body(&JsValue::from_str(&body));
+
+    let request = Request::new_with_str_and_init("http://127.0.0.1:8000/generate/", &opts)
+        .map_err(|e| format!("{:?}", e))?;
+    request
+        .headers()
+        .set("Content-Type", "application/json")
+        .map_err(|e| format!("{:?}", e))?;
+    request
+        .headers()
+        .set("Accept", "text/event-stream")
+        .map_err(|e| format!("{:?}", e))?;
+
+    let window = web_sys::window().ok_or("no window available")?;
+    let resp_value = JsFuture::from(window.fetch_with_request(&request))
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    let response: Response = resp_value.dyn_into().map_err(|e| format!("{:?}", e))?;
+
+    if !response.ok() {
+        return Err(format!("Server responded with status {}", response.status()));
+    }
+
+    let content_type = response
+        .headers()
+        .get("Content-Type")
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    if !content_type.contains("text/event-stream") {
+        let text = JsFuture::from(response.text().map_err(|e| format!("{:?}", e))?)
+            .await
+            .map_err(|e| format!("{:?}", e))?
+            .as_string()
+            .ok_or("response body was not text")?;
+        let api_response: ApiResponse = serde_json::from_str(&text).map_err(|e| format!("Failed to parse response: {}", e))?;
+        return Ok(GenerateOutcome::Json(api_response));
+    }
+
+    let stream_body = response.body().ok_or("streamed response has no body")?;
+    let reader: ReadableStreamDefaultReader = stream_body
+        .get_reader()
+        .dyn_into()
+        .map_err(|e| format!("{:?}", e))?;
+    let decoder = TextDecoder::new().map_err(|e| format!("{:?}", e))?;
+    let decode_opts = TextDecodeOptions::new();
+    decode_opts.set_stream(true);
+
+    // Holds the trailing incomplete line between reads, since chunk boundaries
+    // can split a "data: ..." line in half.
+    let mut pending = String::new();
+    let mut full_answer = String::new();
+
+    loop {
+        let chunk_value = JsFuture::from(reader.read()).await.map_err(|e| format!("{:?}", e))?;
+        let done = Reflect::get(&chunk_value, &JsValue::from_str("done"))
+            .map_err(|e| format!("{:?}", e))?
+            .as_bool()
+            .unwrap_or(true);
+        if done {
+            break;
+        }
+
+        let value = Reflect::get(&chunk_value, &JsValue::from_str("value")).map_err(|e| format!("{:?}", e))?;
+        let array: Uint8Array = value.dyn_into().map_err(|e| format!("{:?}", e))?;
+        // `stream: true` tells the decoder to hold back a multi-byte UTF-8
+        // sequence split across chunk boundaries instead of emitting U+FFFD.
+        let text = decoder
+            .decode_with_buffer_source_and_options(&array, &decode_opts)
+            .map_err(|e| format!("{:?}", e))?;
+        pending.push_str(&text);
+
+        while let Some(newline_pos) = pending.find('\n') {
+            let line = pending[..newline_pos].trim_end_matches('\r').to_string();
+            pending.drain(..=newline_pos);
+
+            let Some(data) = line.strip_prefix("data: ") else {
+                continue;
+            };
+            if data == "[DONE]" {
+                return Ok(GenerateOutcome::Streamed(full_answer));
+            }
+            full_answer.push_str(data);
+            on_token(data);
+        }
+    }
+
+    // Flush any UTF-8 bytes the decoder was withholding, then handle a final
+    // `data: ...` line the server may have sent without a trailing newline.
+    if let Ok(trailing) = decoder.decode() {
+        pending.push_str(&trailing);
+    }
+    if let Some(data) = pending.trim_end_matches('\r').strip_prefix("data: ") {
+        if data != "[DONE]" {
+            full_answer.push_str(data);
+            on_token(data);
+        }
+    }
+
+    Ok(GenerateOutcome::Streamed(full_answer))
+}
 
 // ===== DATA STRUCTURES =====
 
@@ -11,9 +346,18 @@ use wasm_bindgen::JsCast;
 struct QueryRequest {
     query: String,
     top_k: u32,
+    model: String,
+    // Kept for backward compat with servers that still branch on this flag
+    // instead of `model`; derivable from the selected model's backend.
     use_openai: bool,
 }
 
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+struct ModelInfo {
+    id: String,
+    backend: String,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 struct ApiResponse {
     #[allow(dead_code)]
@@ -26,6 +370,15 @@ struct ApiResponse {
     top_k: Option<u32>,
     #[allow(dead_code)]
     timestamp: Option<String>,
+    sources: Option<Vec<SourceDoc>>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+struct SourceDoc {
+    title: String,
+    snippet: String,
+    uri: String,
+    score: f32,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -38,6 +391,15 @@ struct ErrorResponse {
     timestamp: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct HistoryEntry {
+    query: String,
+    answer: String,
+    backend: String,
+    top_k: u32,
+    timestamp: String,
+}
+
 // ===== MAIN APP COMPONENT =====
 
 #[function_component(App)]
@@ -47,29 +409,128 @@ fn app() -> Html {
     let answer_ref = use_node_ref();
     let is_loading = use_state(|| false);
     let error_message = use_state(|| None::<String>);
-    let show_about = use_state(|| false);
-    let show_contacts = use_state(|| false);
-    let use_openai = use_state(|| false);
     let top_k = use_state(|| 5u32);
     let last_backend = use_state(|| String::from("None"));
+    let history = use_state(|| Vec::<HistoryEntry>::new());
+    let show_history = use_state(|| false);
+    let sources = use_state(|| Vec::<SourceDoc>::new());
+    let show_sources = use_state(|| true);
+    let models = use_state(|| Vec::<ModelInfo>::new());
+    let selected_model = use_state(|| String::new());
+    let suggestions = use_state(|| Vec::<String>::new());
+    let location = use_location();
+
+    // Load persisted query history once on mount.
+    {
+        let history = history.clone();
+        use_effect_with((), move |_| {
+            let stored: Vec<HistoryEntry> = LocalStorage::get(HISTORY_STORAGE_KEY).unwrap_or_default();
+            history.set(stored);
+            || ()
+        });
+    }
+
+    // Discover the models the backend currently has available, then — once
+    // that's known — auto-run a search encoded in the URL, so answers are
+    // bookmarkable and shareable. These two have to happen as one effect:
+    // resolving a permalink's `backend` against the model list (to decide
+    // `use_openai`) needs the fetched models in hand, and a URL-supplied
+    // model must win over defaulting the dropdown to `models[0]`.
+    {
+        let query = query.clone();
+        let top_k = top_k.clone();
+        let selected_model = selected_model.clone();
+        let models = models.clone();
+        let answer = answer.clone();
+        let is_loading = is_loading.clone();
+        let error_message = error_message.clone();
+        let last_backend = last_backend.clone();
+        let history = history.clone();
+        let sources = sources.clone();
+        use_effect_with((), move |_| {
+            let permalink = location.as_ref().and_then(|l| l.query::<QueryParams>().ok());
+            spawn_local(async move {
+                let client = Client::new();
+                match client.get("http://127.0.0.1:8000/models").send().await {
+                    Ok(res) => match res.json::<Vec<ModelInfo>>().await {
+                        Ok(fetched_models) => {
+                            let url_model = permalink.as_ref().and_then(|p| p.backend.clone()).filter(|b| !b.is_empty());
+                            let model_value = match url_model {
+                                Some(model) => model,
+                                None => fetched_models.first().map(|m| m.id.clone()).unwrap_or_default(),
+                            };
+                            selected_model.set(model_value.clone());
+                            models.set(fetched_models.clone());
+
+                            let top_k_value = permalink.as_ref().and_then(|p| p.top_k).unwrap_or(5);
+                            if let Some(q) = permalink.and_then(|p| p.q).filter(|q| !q.is_empty()) {
+                                query.set(q.clone());
+                                top_k.set(top_k_value);
+                                run_query(
+                                    q,
+                                    top_k_value,
+                                    model_value,
+                                    fetched_models,
+                                    answer,
+                                    is_loading,
+                                    error_message,
+                                    last_backend,
+                                    history,
+                                    sources,
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            web_sys::console::error_1(&format!("Failed to parse /models response: {}", e).into());
+                        }
+                    },
+                    Err(e) => {
+                        web_sys::console::error_1(&format!("Failed to fetch /models: {}", e).into());
+                    }
+                }
+            });
+            || ()
+        });
+    }
 
-    // Toggle between OpenAI and Ollama
-    let toggle_backend = {
-        let use_openai = use_openai.clone();
+    // Update the selected model when the user picks a different one
+    let on_model_change = {
+        let selected_model = selected_model.clone();
         Callback::from(move |e: Event| {
-            if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
-                use_openai.set(input.checked());
+            if let Some(select) = e.target_dyn_into::<web_sys::HtmlSelectElement>() {
+                selected_model.set(select.value());
             }
         })
     };
 
-    // Handle query input changes
+    // Handle query input changes, refreshing fuzzy autocomplete suggestions as the user types
     let on_input = {
         let query = query.clone();
         let error_message = error_message.clone();
+        let history = history.clone();
+        let suggestions = suggestions.clone();
         Callback::from(move |e: InputEvent| {
             if let Some(input) = e.target_dyn_into::<HtmlInputElement>() {
-                query.set(input.value());
+                let value = input.value();
+
+                if value.is_empty() {
+                    suggestions.set(Vec::new());
+                } else {
+                    let matcher = SkimMatcherV2::default();
+                    let mut scored: Vec<(i64, String)> = history
+                        .iter()
+                        .map(|entry| entry.query.clone())
+                        .filter(|candidate| candidate != &value)
+                        .filter_map(|candidate| {
+                            matcher.fuzzy_match(&candidate, &value).map(|score| (score, candidate))
+                        })
+                        .collect();
+                    scored.sort_by(|a, b| b.0.cmp(&a.0));
+                    scored.dedup_by(|a, b| a.1 == b.1);
+                    suggestions.set(scored.into_iter().take(5).map(|(_, candidate)| candidate).collect());
+                }
+
+                query.set(value);
                 // Clear error when user starts typing
                 if error_message.is_some() {
                     error_message.set(None);
@@ -78,6 +539,16 @@ fn app() -> Html {
         })
     };
 
+    // Fill the query box with a suggestion and dismiss the dropdown
+    let on_suggestion_select = {
+        let query = query.clone();
+        let suggestions = suggestions.clone();
+        Callback::from(move |suggestion: String| {
+            query.set(suggestion);
+            suggestions.set(Vec::new());
+        })
+    };
+
     // Handle top_k slider changes
     let on_top_k_change = {
         let top_k = top_k.clone();
@@ -93,18 +564,23 @@ fn app() -> Html {
     // Handle form submission
     let on_submit = {
         let query = query.clone();
-        let use_openai = use_openai.clone();
+        let models = models.clone();
+        let selected_model = selected_model.clone();
         let top_k = top_k.clone();
         let answer = answer.clone();
         let is_loading = is_loading.clone();
         let error_message = error_message.clone();
         let last_backend = last_backend.clone();
+        let history = history.clone();
+        let sources = sources.clone();
+        let suggestions = suggestions.clone();
 
         Callback::from(move |e: SubmitEvent| {
             e.prevent_default();
-            
+            suggestions.set(Vec::new());
+
             let query_value = (*query).clone().trim().to_string();
-            
+
             // Validation
             if query_value.is_empty() {
                 error_message.set(Some("Please enter a query.".to_string()));
@@ -116,105 +592,74 @@ fn app() -> Html {
                 return;
             }
 
-            // Clear previous error and set loading
-            error_message.set(None);
-            is_loading.set(true);
-            answer.set("Generating answer...".to_string());
-
-            let client = Client::new();
-            let answer = answer.clone();
-            let is_loading = is_loading.clone();
-            let error_message = error_message.clone();
-            let use_openai_value = *use_openai;
             let top_k_value = *top_k;
-            let last_backend = last_backend.clone();
+            let model_value = (*selected_model).clone();
+
+            // Make the search shareable: encode it into the URL as a permalink.
+            let permalink = QueryParams {
+                q: Some(query_value.clone()),
+                top_k: Some(top_k_value),
+                backend: Some(model_value.clone()),
+            };
+            if let Err(e) = BrowserHistory::new().push_with_query("/", &permalink) {
+                web_sys::console::error_1(&format!("Failed to update permalink: {:?}", e).into());
+            }
 
-            spawn_local(async move {
-                let payload = QueryRequest {
-                    query: query_value.clone(),
-                    top_k: top_k_value,
-                    use_openai: use_openai_value,
-                };
-
-                let response = client
-                    .post("http://127.0.0.1:8000/generate/")
-                    .json(&payload)
-                    .send()
-                    .await;
-
-                match response {
-                    Ok(res) => {
-                        let status = res.status();
-                        
-                        if status.is_success() {
-                            // Try to parse successful response
-                            match res.json::<ApiResponse>().await {
-                                Ok(api_response) => {
-                                    // Log the response for debugging
-                                    web_sys::console::log_1(&format!("Received answer: {}", &api_response.answer).into());
-                                    answer.set(api_response.answer);
-                                    if let Some(backend) = api_response.backend {
-                                        last_backend.set(backend);
-                                    }
-                                    error_message.set(None);
-                                }
-                                Err(e) => {
-                                    web_sys::console::error_1(&format!("Parse error: {:?}", e).into());
-                                    error_message.set(Some(format!("Failed to parse response: {}", e)));
-                                    answer.set("Error parsing response.".to_string());
-                                }
-                            }
-                        } else {
-                            // Try to parse error response
-                            match res.json::<ErrorResponse>().await {
-                                Ok(error_resp) => {
-                                    let error_msg = if let Some(details) = error_resp.details {
-                                        format!("{}: {}", error_resp.error, details)
-                                    } else {
-                                        error_resp.error
-                                    };
-                                    error_message.set(Some(error_msg));
-                                    answer.set("Failed to generate answer.".to_string());
-                                }
-                                Err(e) => {
-                                    error_message.set(Some(format!("Server error ({}): {}", status, e)));
-                                    answer.set("Server error.".to_string());
-                                }
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        error_message.set(Some(format!("Connection error: {}. Make sure the API server is running.", e)));
-                        answer.set("Error connecting to the API.".to_string());
-                    }
-                }
+            run_query(
+                query_value,
+                top_k_value,
+                model_value,
+                (*models).clone(),
+                answer.clone(),
+                is_loading.clone(),
+                error_message.clone(),
+                last_backend.clone(),
+                history.clone(),
+                sources.clone(),
+            );
+        })
+    };
 
-                is_loading.set(false);
-            });
+    // Toggle the history sidebar
+    let on_history_toggle = {
+        let show_history = show_history.clone();
+        Callback::from(move |_| {
+            show_history.set(!(*show_history));
         })
     };
 
-    // Toggle about section
-    let on_about_click = {
-        let show_about = show_about.clone();
-        let show_contacts = show_contacts.clone();
+    // Clear the persisted history
+    let on_clear_history = {
+        let history = history.clone();
         Callback::from(move |_| {
-            show_about.set(!(*show_about));
-            if *show_about {
-                show_contacts.set(false);
-            }
+            LocalStorage::delete(HISTORY_STORAGE_KEY);
+            history.set(Vec::new());
         })
     };
 
-    // Toggle contacts section
-    let on_contacts_click = {
-        let show_contacts = show_contacts.clone();
-        let show_about = show_about.clone();
+    // Re-display a past entry from history without a network round-trip
+    let on_history_select = {
+        let query = query.clone();
+        let answer = answer.clone();
+        let last_backend = last_backend.clone();
+        let top_k = top_k.clone();
+        let error_message = error_message.clone();
+        let sources = sources.clone();
+        Callback::from(move |entry: HistoryEntry| {
+            query.set(entry.query);
+            answer.set(entry.answer);
+            last_backend.set(entry.backend);
+            top_k.set(entry.top_k);
+            error_message.set(None);
+            sources.set(Vec::new());
+        })
+    };
+
+    // Toggle the "Sources" section
+    let on_sources_toggle = {
+        let show_sources = show_sources.clone();
         Callback::from(move |_| {
-            show_contacts.set(!(*show_contacts));
-            if *show_contacts {
-                show_about.set(false);
-            }
+            show_sources.set(!(*show_sources));
         })
     };
 
@@ -222,9 +667,10 @@ fn app() -> Html {
     {
         let answer = answer.clone();
         let answer_ref = answer_ref.clone();
-        use_effect_with(answer.clone(), move |answer_val| {
+        let source_count = sources.len();
+        use_effect_with((answer.clone(), source_count), move |(answer_val, source_count)| {
             if let Some(element) = answer_ref.cast::<Element>() {
-                element.set_inner_html(&**answer_val);
+                element.set_inner_html(&render_answer_html(answer_val, *source_count));
             }
             || ()
         });
@@ -235,29 +681,62 @@ fn app() -> Html {
             <header class="header">
                 <h1>{ "🔍 RAG Historical Events Explorer" }</h1>
                 <p class="subtitle">{ "Ask questions about 20th century historical events" }</p>
+                <button class="history-toggle-button" onclick={on_history_toggle}>
+                    { format!("📜 History ({})", history.len()) }
+                </button>
             </header>
 
+            { if *show_history {
+                html! {
+                    <aside class="history-sidebar">
+                        <div class="history-sidebar-header">
+                            <h2>{ "Query History" }</h2>
+                            <button class="clear-history-button" onclick={on_clear_history}>
+                                { "🗑️ Clear history" }
+                            </button>
+                        </div>
+                        { if history.is_empty() {
+                            html! { <p class="history-empty">{ "No past queries yet." }</p> }
+                        } else {
+                            html! {
+                                <ul class="history-list">
+                                    { for history.iter().rev().cloned().map(|entry| {
+                                        let on_history_select = on_history_select.clone();
+                                        let entry_for_click = entry.clone();
+                                        html! {
+                                            <li
+                                                class="history-item"
+                                                onclick={Callback::from(move |_| on_history_select.emit(entry_for_click.clone()))}
+                                            >
+                                                <span class="history-item-query">{ &entry.query }</span>
+                                                <span class="history-item-meta">{ format!("{} · top_k={}", entry.backend, entry.top_k) }</span>
+                                            </li>
+                                        }
+                                    }) }
+                                </ul>
+                            }
+                        }}
+                    </aside>
+                }
+            } else {
+                html! {}
+            }}
+
             <main class="main-content">
                 <div class="container">
-                    // Backend Toggle
-                    <div class="backend-toggle">
-                        <label class="switch">
-                            <input
-                                type="checkbox"
-                                id="backend-toggle"
-                                name="backend-toggle"
-                                checked={*use_openai}
-                                onchange={toggle_backend}
-                            />
-                            <span class="slider"></span>
-                        </label>
-                        <label for="backend-toggle" class="toggle-label">
-                            { if *use_openai {
-                                "🤖 OpenAI GPT-4 Backend"
-                            } else {
-                                "🦙 Local Ollama Backend (phi3:mini)"
-                            }}
-                        </label>
+                    // Model Picker
+                    <div class="model-picker">
+                        <label for="model-select" class="form-label">{ "Model:" }</label>
+                        <select id="model-select" name="model-select" onchange={on_model_change}>
+                            { for models.iter().map(|m| {
+                                let label = format!("{} ({})", m.id, m.backend);
+                                html! {
+                                    <option value={m.id.clone()} selected={*selected_model == m.id}>
+                                        { label }
+                                    </option>
+                                }
+                            }) }
+                        </select>
                     </div>
 
                     // Query Form
@@ -279,6 +758,26 @@ fn app() -> Html {
                             <div class="char-counter">
                                 { format!("{}/500 characters", (*query).len()) }
                             </div>
+                            { if !suggestions.is_empty() {
+                                html! {
+                                    <ul class="suggestions-dropdown">
+                                        { for suggestions.iter().cloned().map(|suggestion| {
+                                            let on_suggestion_select = on_suggestion_select.clone();
+                                            let suggestion_for_click = suggestion.clone();
+                                            html! {
+                                                <li
+                                                    class="suggestion-item"
+                                                    onclick={Callback::from(move |_| on_suggestion_select.emit(suggestion_for_click.clone()))}
+                                                >
+                                                    { suggestion }
+                                                </li>
+                                            }
+                                        }) }
+                                    </ul>
+                                }
+                            } else {
+                                html! {}
+                            }}
                         </div>
 
                         <div class="form-group">
@@ -358,87 +857,143 @@ fn app() -> Html {
                         }}
                     </div>
 
-                    // Action Buttons
-                    <div class="action-buttons">
-                        <button
-                            class={classes!("action-button", show_about.then(|| "active"))}
-                            onclick={on_about_click}
-                        >
-                            { "ℹ️ About" }
-                        </button>
-                        <button
-                            class={classes!("action-button", show_contacts.then(|| "active"))}
-                            onclick={on_contacts_click}
-                        >
-                            { "📧 Contacts" }
-                        </button>
-                    </div>
-
-                    // About Section
-                    { if *show_about {
+                    // Sources / Citations
+                    { if !sources.is_empty() {
                         html! {
-                            <div class="info-section about">
-                                <h2>{ "About This Project" }</h2>
-                                <p>{ "This is a Retrieval-Augmented Generation (RAG) system built with modern technologies:" }</p>
-                                <ul>
-                                    <li><strong>{ "Frontend:" }</strong>{ " Rust + Yew (WebAssembly)" }</li>
-                                    <li><strong>{ "Backend:" }</strong>{ " Python + Flask" }</li>
-                                    <li><strong>{ "Data Source:" }</strong>{ " DBpedia (20th century historical events)" }</li>
-                                    <li><strong>{ "Embeddings:" }</strong>{ " sentence-transformers (all-MiniLM-L6-v2)" }</li>
-                                    <li><strong>{ "Vector Search:" }</strong>{ " FAISS" }</li>
-                                    <li><strong>{ "LLMs:" }</strong>{ " Ollama (phi3:mini) or OpenAI (GPT-4)" }</li>
-                                </ul>
-                                <p><strong>{ "Version:" }</strong>{ " 1.0.0" }</p>
-                                <p class="feature-note">
-                                    { "The system retrieves relevant historical documents and uses them as context for generating accurate, grounded answers." }
-                                </p>
+                            <div class="sources-section">
+                                <button class="sources-toggle-button" onclick={on_sources_toggle}>
+                                    { format!("{} Sources ({})", if *show_sources { "▾" } else { "▸" }, sources.len()) }
+                                </button>
+                                { if *show_sources {
+                                    html! {
+                                        <ol class="sources-list">
+                                            { for sources.iter().enumerate().map(|(i, source)| {
+                                                html! {
+                                                    <li id={format!("source-{}", i + 1)} class="source-item">
+                                                        { if let Some(href) = safe_external_href(&source.uri) {
+                                                            html! {
+                                                                <a href={href.to_string()} target="_blank" rel="noopener noreferrer">
+                                                                    { &source.title }
+                                                                </a>
+                                                            }
+                                                        } else {
+                                                            html! { <span>{ &source.title }</span> }
+                                                        }}
+                                                        <p class="source-snippet">{ &source.snippet }</p>
+                                                        <span class="source-score">{ format!("similarity: {:.3}", source.score) }</span>
+                                                    </li>
+                                                }
+                                            }) }
+                                        </ol>
+                                    }
+                                } else {
+                                    html! {}
+                                }}
                             </div>
                         }
                     } else {
                         html! {}
                     }}
 
-                    // Contacts Section
-                    { if *show_contacts {
-                        html! {
-                            <div class="info-section contacts">
-                                <h2>{ "Contact the Developers" }</h2>
-                                <div class="contact-grid">
-                                    <div class="contact-card">
-                                        <h3>{ "👨‍💻 Francesco" }</h3>
-                                        <p>
-                                            <a href="https://github.com/frontinus" target="_blank" rel="noopener noreferrer">
-                                                { "🐙 GitHub" }
-                                            </a>
-                                        </p>
-                                        <p>
-                                            <a href="https://linkedin.com/in/francesco-abate-79601719b" target="_blank" rel="noopener noreferrer">
-                                                { "💼 LinkedIn" }
-                                            </a>
-                                        </p>
-                                    </div>
-                                    <div class="contact-card">
-                                        <h3>{ "👨‍💻 Thomas" }</h3>
-                                        <p>
-                                            <a href="https://github.com/thetom061" target="_blank" rel="noopener noreferrer">
-                                                { "🐙 GitHub" }
-                                            </a>
-                                        </p>
-                                        <p>
-                                            <a href="https://www.linkedin.com/in/thomas-cotte-9870531a1/" target="_blank" rel="noopener noreferrer">
-                                                { "💼 LinkedIn" }
-                                            </a>
-                                        </p>
-                                    </div>
-                                </div>
-                            </div>
-                        }
-                    } else {
-                        html! {}
-                    }}
+                    // Navigation
+                    <div class="action-buttons">
+                        <Link<Route> classes={classes!("action-button")} to={Route::About}>
+                            { "ℹ️ About" }
+                        </Link<Route>>
+                        <Link<Route> classes={classes!("action-button")} to={Route::Contacts}>
+                            { "📧 Contacts" }
+                        </Link<Route>>
+                    </div>
+                </div>
+            </main>
+
+            <footer class="footer">
+                <p>{ "Built with ❤️ using Rust, Python, and AI" }</p>
+            </footer>
+        </div>
+    }
+}
+
+// ===== STATIC PAGES =====
+
+#[function_component(AboutPage)]
+fn about_page() -> Html {
+    html! {
+        <div class="app-container">
+            <header class="header">
+                <h1>{ "🔍 RAG Historical Events Explorer" }</h1>
+                <Link<Route> classes={classes!("action-button")} to={Route::Home}>{ "← Back to search" }</Link<Route>>
+            </header>
+            <main class="main-content">
+                <div class="container">
+                    <div class="info-section about">
+                        <h2>{ "About This Project" }</h2>
+                        <p>{ "This is a Retrieval-Augmented Generation (RAG) system built with modern technologies:" }</p>
+                        <ul>
+                            <li><strong>{ "Frontend:" }</strong>{ " Rust + Yew (WebAssembly)" }</li>
+                            <li><strong>{ "Backend:" }</strong>{ " Python + Flask" }</li>
+                            <li><strong>{ "Data Source:" }</strong>{ " DBpedia (20th century historical events)" }</li>
+                            <li><strong>{ "Embeddings:" }</strong>{ " sentence-transformers (all-MiniLM-L6-v2)" }</li>
+                            <li><strong>{ "Vector Search:" }</strong>{ " FAISS" }</li>
+                            <li><strong>{ "LLMs:" }</strong>{ " Ollama (phi3:mini) or OpenAI (GPT-4)" }</li>
+                        </ul>
+                        <p><strong>{ "Version:" }</strong>{ " 1.0.0" }</p>
+                        <p class="feature-note">
+                            { "The system retrieves relevant historical documents and uses them as context for generating accurate, grounded answers." }
+                        </p>
+                    </div>
                 </div>
             </main>
+            <footer class="footer">
+                <p>{ "Built with ❤️ using Rust, Python, and AI" }</p>
+            </footer>
+        </div>
+    }
+}
 
+#[function_component(ContactsPage)]
+fn contacts_page() -> Html {
+    html! {
+        <div class="app-container">
+            <header class="header">
+                <h1>{ "🔍 RAG Historical Events Explorer" }</h1>
+                <Link<Route> classes={classes!("action-button")} to={Route::Home}>{ "← Back to search" }</Link<Route>>
+            </header>
+            <main class="main-content">
+                <div class="container">
+                    <div class="info-section contacts">
+                        <h2>{ "Contact the Developers" }</h2>
+                        <div class="contact-grid">
+                            <div class="contact-card">
+                                <h3>{ "👨‍💻 Francesco" }</h3>
+                                <p>
+                                    <a href="https://github.com/frontinus" target="_blank" rel="noopener noreferrer">
+                                        { "🐙 GitHub" }
+                                    </a>
+                                </p>
+                                <p>
+                                    <a href="https://linkedin.com/in/francesco-abate-79601719b" target="_blank" rel="noopener noreferrer">
+                                        { "💼 LinkedIn" }
+                                    </a>
+                                </p>
+                            </div>
+                            <div class="contact-card">
+                                <h3>{ "👨‍💻 Thomas" }</h3>
+                                <p>
+                                    <a href="https://github.com/thetom061" target="_blank" rel="noopener noreferrer">
+                                        { "🐙 GitHub" }
+                                    </a>
+                                </p>
+                                <p>
+                                    <a href="https://www.linkedin.com/in/thomas-cotte-9870531a1/" target="_blank" rel="noopener noreferrer">
+                                        { "💼 LinkedIn" }
+                                    </a>
+                                </p>
+                            </div>
+                        </div>
+                    </div>
+                </div>
+            </main>
             <footer class="footer">
                 <p>{ "Built with ❤️ using Rust, Python, and AI" }</p>
             </footer>
@@ -446,6 +1001,17 @@ fn app() -> Html {
     }
 }
 
+// ===== ROOT COMPONENT =====
+
+#[function_component(Root)]
+fn root() -> Html {
+    html! {
+        <BrowserRouter>
+            <Switch<Route> render={switch} />
+        </BrowserRouter>
+    }
+}
+
 fn main() {
-    yew::Renderer::<App>::new().render();
+    yew::Renderer::<Root>::new().render();
 }
\ No newline at end of file